@@ -1,10 +1,55 @@
 use std::collections::HashMap;
+use std::fmt::{self, Write as _};
 use std::iter::Peekable;
 
+/// The specific kind of failure encountered while parsing JSON.
+///
+/// Mirrors the `ErrorCode` enum from rustc's `libserialize` JSON module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    InvalidSyntax,
+    InvalidNumber,
+    EOFWhileParsingObject,
+    EOFWhileParsingArray,
+    EOFWhileParsingValue,
+    EOFWhileParsingString,
+    KeyMustBeAString,
+    ExpectedColon,
+    ExpectedComma,
+    TrailingCharacters,
+    InvalidEscape,
+    UnexpectedEndOfHexEscape,
+    LoneLeadingSurrogateInHexEscape,
+    InvalidUnicodeCodePoint,
+}
+
+/// An error produced while parsing, together with the line and column it
+/// occurred at (both 1-indexed).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParserError {
+    pub code: ErrorCode,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl ParserError {
+    fn new(code: ErrorCode, line: usize, column: usize) -> Self {
+        ParserError { code, line, column }
+    }
+}
+
+impl fmt::Display for ParserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} at line {} column {}", self.code, self.line, self.column)
+    }
+}
+
+impl std::error::Error for ParserError {}
+
 #[derive(Debug)]
 pub enum Node {
-    IntLiteral(i32),
-    FloatLiteral(f32),
+    IntLiteral(i64),
+    FloatLiteral(f64),
     StringLiteral(String),
     NullLiteral,
     BoolLiteral(bool),
@@ -12,133 +57,819 @@ pub enum Node {
     Array(Vec<Node>),
 }
 
+impl Node {
+    /// Serializes this node into pretty-printed JSON, with nested
+    /// `Object`/`Array` members indented by `indent` spaces per level and one
+    /// member per line.
+    pub fn to_string_pretty(&self, indent: usize) -> String {
+        let mut out = String::new();
+        write_pretty(self, &mut out, indent, 0).expect("writing to a String never fails");
+        out
+    }
 
-pub struct Parser<T: Iterator<Item = char>> {
-    json: Peekable<T>
+    /// Looks up `key` in this node's `Object` member, or `None` if this isn't
+    /// an object or has no such key.
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        match self {
+            Node::Object(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    /// Looks up `index` in this node's `Array` member, or `None` if this
+    /// isn't an array or the index is out of bounds.
+    pub fn at(&self, index: usize) -> Option<&Node> {
+        match self {
+            Node::Array(items) => items.get(index),
+            _ => None,
+        }
+    }
+
+    /// Resolves a slash-delimited path against this node, e.g.
+    /// `"data/items/0/name"`. Each segment is looked up with [`Node::get`] if
+    /// the current node is an object, or parsed as an index and looked up
+    /// with [`Node::at`] if it's an array. Returns `None` as soon as a
+    /// segment can't be resolved.
+    pub fn pointer(&self, path: &str) -> Option<&Node> {
+        path.split('/').filter(|segment| !segment.is_empty()).try_fold(self, |node, segment| match node {
+            Node::Object(_) => node.get(segment),
+            Node::Array(_) => segment.parse().ok().and_then(|index: usize| node.at(index)),
+            _ => None,
+        })
+    }
+
+    /// Returns the wrapped integer, or `None` if this isn't an `IntLiteral`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Node::IntLiteral(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the wrapped float, or `None` if this isn't a `FloatLiteral`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Node::FloatLiteral(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// Returns the wrapped bool, or `None` if this isn't a `BoolLiteral`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Node::BoolLiteral(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the wrapped string, or `None` if this isn't a `StringLiteral`.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Node::StringLiteral(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the wrapped array, or `None` if this isn't an `Array`.
+    pub fn as_array(&self) -> Option<&Vec<Node>> {
+        match self {
+            Node::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the wrapped object, or `None` if this isn't an `Object`.
+    pub fn as_object(&self) -> Option<&HashMap<String, Node>> {
+        match self {
+            Node::Object(map) => Some(map),
+            _ => None,
+        }
+    }
 }
 
-impl<T: Iterator<Item = char>> Parser<T> {
+impl fmt::Display for Node {
+    /// Writes this node as compact JSON, with no extra whitespace.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_compact(self, f)
+    }
+}
+
+fn write_compact(node: &Node, out: &mut impl fmt::Write) -> fmt::Result {
+    match node {
+        Node::IntLiteral(n) => write!(out, "{}", n),
+        Node::FloatLiteral(value) => out.write_str(&format_float(*value)),
+        Node::StringLiteral(s) => write_escaped_str(out, s),
+        Node::NullLiteral => out.write_str("null"),
+        Node::BoolLiteral(b) => out.write_str(if *b { "true" } else { "false" }),
+        Node::Object(map) => {
+            out.write_char('{')?;
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            for (i, key) in keys.iter().enumerate() {
+                if i > 0 {
+                    out.write_char(',')?;
+                }
+                write_escaped_str(out, key)?;
+                out.write_char(':')?;
+                write_compact(&map[*key], out)?;
+            }
+            out.write_char('}')
+        }
+        Node::Array(items) => {
+            out.write_char('[')?;
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.write_char(',')?;
+                }
+                write_compact(item, out)?;
+            }
+            out.write_char(']')
+        }
+    }
+}
+
+fn write_pretty(node: &Node, out: &mut String, indent: usize, depth: usize) -> fmt::Result {
+    match node {
+        Node::Object(map) if !map.is_empty() => {
+            out.push_str("{\n");
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            let inner_depth = depth + 1;
+            for (i, key) in keys.iter().enumerate() {
+                out.push_str(&" ".repeat(indent * inner_depth));
+                write_escaped_str(out, key)?;
+                out.push_str(": ");
+                write_pretty(&map[*key], out, indent, inner_depth)?;
+                if i + 1 < keys.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            out.push_str(&" ".repeat(indent * depth));
+            out.push('}');
+            Ok(())
+        }
+        Node::Object(_) => out.write_str("{}"),
+        Node::Array(items) if !items.is_empty() => {
+            out.push_str("[\n");
+            let inner_depth = depth + 1;
+            for (i, item) in items.iter().enumerate() {
+                out.push_str(&" ".repeat(indent * inner_depth));
+                write_pretty(item, out, indent, inner_depth)?;
+                if i + 1 < items.len() {
+                    out.push(',');
+                }
+                out.push('\n');
+            }
+            out.push_str(&" ".repeat(indent * depth));
+            out.push(']');
+            Ok(())
+        }
+        Node::Array(_) => out.write_str("[]"),
+        other => write_compact(other, out),
+    }
+}
+
+/// Writes `s` as a JSON string literal, escaping control characters, quotes,
+/// and backslashes the way `StringLiteral` output must round-trip.
+fn write_escaped_str(out: &mut impl fmt::Write, s: &str) -> fmt::Result {
+    out.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\t' => out.write_str("\\t")?,
+            '\r' => out.write_str("\\r")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+/// Formats an `f64` so the output always contains a decimal point, ensuring
+/// it round-trips back to a float rather than an int (e.g. `1.0`, not `1`).
+fn format_float(value: f64) -> String {
+    let mut s = format!("{}", value);
+    if !s.contains(['.', 'e', 'E']) {
+        s.push_str(".0");
+    }
+    s
+}
+
+
+/// One step of a JSON document as produced by [`StreamingParser`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonEvent {
+    ObjectStart,
+    ObjectEnd,
+    ArrayStart,
+    ArrayEnd,
+    BooleanValue(bool),
+    IntValue(i64),
+    FloatValue(f64),
+    StringValue(String),
+    NullValue,
+}
+
+/// One frame of the key/index path reported by [`StreamingParser::stack`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum StackElement {
+    Key(String),
+    Index(usize),
+}
+
+/// The parse state of an open `Object`: whether the next thing the scanner
+/// sees should be a key (or the closing `}`), or the `,` that follows a
+/// member's value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ObjectState {
+    AwaitingKeyOrEnd,
+    AwaitingSeparator,
+}
+
+/// The parse state of an open `Array`, mirroring [`ObjectState`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArrayState {
+    AwaitingValueOrEnd,
+    AwaitingSeparator,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frame {
+    Object(ObjectState),
+    Array(ArrayState),
+}
+
+enum NumberValue {
+    Int(i64),
+    Float(f64),
+}
+
+/// A pull (SAX-style) JSON parser: each call to `next()` scans just enough
+/// input to produce the next [`JsonEvent`], without building a tree in
+/// memory. `stack()` reports the key/index path of the current position in
+/// the document.
+pub struct StreamingParser<T: Iterator<Item = char>> {
+    json: Peekable<T>,
+    line: usize,
+    column: usize,
+    frames: Vec<Frame>,
+    path: Vec<StackElement>,
+    started: bool,
+    errored: bool,
+}
+
+impl<T: Iterator<Item = char>> StreamingParser<T> {
     pub fn new(json: T) -> Self {
         Self {
-            json: json.peekable()
+            json: json.peekable(),
+            line: 1,
+            column: 1,
+            frames: Vec::new(),
+            path: Vec::new(),
+            started: false,
+            errored: false,
         }
     }
 
-    pub fn parse(&mut self) -> Node {
-        self.value()
+    /// The key/index path from the document root to the current position.
+    /// After a value event, it ends with the key or index that value belongs
+    /// to. After an `ObjectStart` or `ArrayStart`, it already holds one more
+    /// frame for the new container's first member: an empty key or index 0.
+    /// After an `ObjectEnd` or `ArrayEnd`, that frame has been removed again.
+    pub fn stack(&self) -> &[StackElement] {
+        &self.path
+    }
+
+    /// Consumes and returns the next character, keeping `line`/`column` in sync.
+    fn bump(&mut self) -> Option<char> {
+        let next = self.json.next();
+        if let Some(c) = next {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        next
+    }
+
+    fn error(&self, code: ErrorCode) -> ParserError {
+        ParserError::new(code, self.line, self.column)
+    }
+
+    /// Bumps the next character and errors with `eof_code` if the input is
+    /// already exhausted, or `ErrorCode::InvalidSyntax` if it doesn't match
+    /// `expected`.
+    fn expect(&mut self, expected: char, eof_code: ErrorCode) -> Result<(), ParserError> {
+        match self.bump() {
+            Some(c) if c == expected => Ok(()),
+            Some(_) => Err(self.error(ErrorCode::InvalidSyntax)),
+            None => Err(self.error(eof_code)),
+        }
     }
 
     fn skip_space(&mut self) {
         while let Some(' ' | '\n' | '\t') = self.json.peek() {
-            self.json.next();
+            self.bump();
         }
     }
 
-    fn value(&mut self) -> Node {
+    /// Scans one JSON value (scalar or container opener) and produces its event.
+    fn value_event(&mut self) -> Result<JsonEvent, ParserError> {
         self.skip_space();
-        match self.json.peek().unwrap() {
-            '0'..='9' => self.number(),
-            '"' => Node::StringLiteral(self.string()),
-            '{' => self.object(),
-            '[' => self.array(),
-            'n' => self.null(),
-            't' => self.parse_true(),
-            'f' => self.parse_false(),
-            _ => panic!("unexpected character")
-        }
-    }
-
-    fn number(&mut self) -> Node {
-        let mut ret: Vec<char> = Vec::new();
-        while let Some(num) = self.json.peek() {
-            if let '0'..='9' | '.' = num {
-                ret.push(*num);
-                self.json.next();
-            } else {
-                break;
+        match self.json.peek() {
+            Some('0'..='9' | '-') => match self.number()? {
+                NumberValue::Int(n) => Ok(JsonEvent::IntValue(n)),
+                NumberValue::Float(f) => Ok(JsonEvent::FloatValue(f)),
+            },
+            Some('"') => Ok(JsonEvent::StringValue(self.string()?)),
+            Some('{') => {
+                self.bump();
+                self.frames.push(Frame::Object(ObjectState::AwaitingKeyOrEnd));
+                self.path.push(StackElement::Key(String::new()));
+                Ok(JsonEvent::ObjectStart)
+            }
+            Some('[') => {
+                self.bump();
+                self.frames.push(Frame::Array(ArrayState::AwaitingValueOrEnd));
+                self.path.push(StackElement::Index(0));
+                Ok(JsonEvent::ArrayStart)
+            }
+            Some('n') => {
+                self.literal("null")?;
+                Ok(JsonEvent::NullValue)
             }
+            Some('t') => {
+                self.literal("true")?;
+                Ok(JsonEvent::BooleanValue(true))
+            }
+            Some('f') => {
+                self.literal("false")?;
+                Ok(JsonEvent::BooleanValue(false))
+            }
+            Some(_) => Err(self.error(ErrorCode::InvalidSyntax)),
+            None => Err(self.error(ErrorCode::EOFWhileParsingValue)),
         }
-        let number: String = ret.iter().collect();
-        if let Ok(num) = number.parse::<i32>() {
-            return Node::IntLiteral(num);
-        } else if let Ok(num) = number.parse::<f32>() {
-            return Node::FloatLiteral(num);
-        } else {
-            panic!("failed to parse number");
+    }
+
+    /// Expects each character of `lit` in turn (used for `null`/`true`/`false`).
+    fn literal(&mut self, lit: &str) -> Result<(), ParserError> {
+        for expected in lit.chars() {
+            self.expect(expected, ErrorCode::EOFWhileParsingValue)?;
+        }
+        Ok(())
+    }
+
+    /// Parses the full JSON number grammar: an optional leading `-`, an
+    /// integer part, an optional fractional part, and an optional exponent.
+    fn number(&mut self) -> Result<NumberValue, ParserError> {
+        let mut buf = String::new();
+        let mut is_float = false;
+
+        if self.json.peek() == Some(&'-') {
+            buf.push('-');
+            self.bump();
+        }
+
+        match self.json.peek() {
+            Some('0') => {
+                buf.push('0');
+                self.bump();
+                if let Some('0'..='9') = self.json.peek() {
+                    return Err(self.error(ErrorCode::InvalidNumber));
+                }
+            }
+            Some('1'..='9') => self.digits(&mut buf),
+            _ => return Err(self.error(ErrorCode::InvalidNumber)),
+        }
+
+        if self.json.peek() == Some(&'.') {
+            is_float = true;
+            buf.push('.');
+            self.bump();
+            if !matches!(self.json.peek(), Some('0'..='9')) {
+                return Err(self.error(ErrorCode::InvalidNumber));
+            }
+            self.digits(&mut buf);
         }
+
+        if let Some('e' | 'E') = self.json.peek() {
+            is_float = true;
+            buf.push(self.bump().unwrap());
+            if let Some('+' | '-') = self.json.peek() {
+                buf.push(self.bump().unwrap());
+            }
+            if !matches!(self.json.peek(), Some('0'..='9')) {
+                return Err(self.error(ErrorCode::InvalidNumber));
+            }
+            self.digits(&mut buf);
+        }
+
+        if !is_float {
+            if let Ok(n) = buf.parse::<i64>() {
+                return Ok(NumberValue::Int(n));
+            }
+        }
+        buf.parse::<f64>()
+            .map(NumberValue::Float)
+            .map_err(|_| self.error(ErrorCode::InvalidNumber))
     }
 
-    fn string(&mut self) -> String {
-        self.json.next();
-        let mut ret: Vec<char> = Vec::new();
-        while let Some(each_char) = self.json.next() {
-            if each_char == '"' {break;}
-            ret.push(each_char)
+    /// Consumes a run of ASCII digits into `buf`.
+    fn digits(&mut self, buf: &mut String) {
+        while let Some(c @ '0'..='9') = self.json.peek() {
+            buf.push(*c);
+            self.bump();
         }
-        ret.iter().collect()
     }
 
-    fn object(&mut self) -> Node {
-        self.json.next();
-        let mut ret: HashMap<String, Node> = HashMap::new();
+    fn string(&mut self) -> Result<String, ParserError> {
+        self.bump();
+        let mut ret = String::new();
         loop {
-            self.skip_space();
-            if self.json.peek() != Some(&'"') {
-                assert!(self.json.next() == Some('}'));
-                break;
+            match self.bump() {
+                Some('"') => break,
+                Some('\\') => ret.push(self.escape()?),
+                Some(c) => ret.push(c),
+                None => return Err(self.error(ErrorCode::EOFWhileParsingString)),
             }
-            let key = self.string();
-            self.skip_space();
-            assert!(self.json.next() == Some(':'), "expect : ");
-            let value = self.value();
-            assert!(self.json.next() == Some(','), "expect , in parsing object");
-            ret.insert(key, value);
-        };
-        Node::Object(ret)
+        }
+        Ok(ret)
+    }
+
+    /// Parses the character(s) following a `\` inside a string, per the JSON
+    /// escape grammar (`\"`, `\\`, `\/`, `\b`, `\f`, `\n`, `\r`, `\t`, and
+    /// `\uXXXX`, including surrogate pairs).
+    fn escape(&mut self) -> Result<char, ParserError> {
+        match self.bump() {
+            Some('"') => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some('/') => Ok('/'),
+            Some('b') => Ok('\u{8}'),
+            Some('f') => Ok('\u{c}'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some('u') => self.unicode_escape(),
+            Some(_) => Err(self.error(ErrorCode::InvalidEscape)),
+            None => Err(self.error(ErrorCode::EOFWhileParsingString)),
+        }
+    }
+
+    /// Reads exactly four hex digits into a `u16` code unit.
+    fn hex4(&mut self) -> Result<u16, ParserError> {
+        let mut value: u16 = 0;
+        for _ in 0..4 {
+            let digit = match self.bump() {
+                Some(c) => c
+                    .to_digit(16)
+                    .ok_or_else(|| self.error(ErrorCode::InvalidEscape))?,
+                None => return Err(self.error(ErrorCode::UnexpectedEndOfHexEscape)),
+            };
+            value = value * 16 + digit as u16;
+        }
+        Ok(value)
     }
 
-    fn array(&mut self) -> Node {
-        self.json.next();
-        let mut ret: Vec<Node> = Vec::new();
+    /// Parses a `\uXXXX` escape, combining a high/low surrogate pair into a
+    /// single `char` when one is present.
+    fn unicode_escape(&mut self) -> Result<char, ParserError> {
+        let unit = self.hex4()?;
+        if (0xD800..=0xDBFF).contains(&unit) {
+            if self.bump() != Some('\\') || self.bump() != Some('u') {
+                return Err(self.error(ErrorCode::LoneLeadingSurrogateInHexEscape));
+            }
+            let low = self.hex4()?;
+            if !(0xDC00..=0xDFFF).contains(&low) {
+                return Err(self.error(ErrorCode::LoneLeadingSurrogateInHexEscape));
+            }
+            let combined = 0x10000 + (((unit - 0xD800) as u32) << 10) + (low - 0xDC00) as u32;
+            char::from_u32(combined).ok_or_else(|| self.error(ErrorCode::InvalidUnicodeCodePoint))
+        } else if (0xDC00..=0xDFFF).contains(&unit) {
+            Err(self.error(ErrorCode::LoneLeadingSurrogateInHexEscape))
+        } else {
+            char::from_u32(unit as u32).ok_or_else(|| self.error(ErrorCode::InvalidUnicodeCodePoint))
+        }
+    }
+}
+
+impl<T: Iterator<Item = char>> Iterator for StreamingParser<T> {
+    type Item = Result<JsonEvent, ParserError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.errored {
+            return None;
+        }
         loop {
-            self.skip_space();
-            if self.json.peek() == Some(&']') {
-                self.json.next();
-                break;
+            let event = match self.frames.last().copied() {
+                None => {
+                    if self.started {
+                        return None;
+                    }
+                    self.started = true;
+                    self.value_event()
+                }
+                Some(Frame::Object(ObjectState::AwaitingKeyOrEnd)) => {
+                    self.skip_space();
+                    match self.json.peek() {
+                        None => Err(self.error(ErrorCode::EOFWhileParsingObject)),
+                        Some('}') => self.expect('}', ErrorCode::EOFWhileParsingObject).map(|()| {
+                            self.frames.pop();
+                            self.path.pop();
+                            JsonEvent::ObjectEnd
+                        }),
+                        Some('"') => self
+                            .string()
+                            .and_then(|key| {
+                                *self.path.last_mut().unwrap() = StackElement::Key(key);
+                                self.skip_space();
+                                self.expect(':', ErrorCode::EOFWhileParsingObject).map_err(|e| {
+                                    ParserError::new(ErrorCode::ExpectedColon, e.line, e.column)
+                                })
+                            })
+                            .and_then(|()| {
+                                *self.frames.last_mut().unwrap() =
+                                    Frame::Object(ObjectState::AwaitingSeparator);
+                                self.value_event()
+                            }),
+                        Some(_) => Err(self.error(ErrorCode::KeyMustBeAString)),
+                    }
+                }
+                Some(Frame::Object(ObjectState::AwaitingSeparator)) => match self.bump() {
+                    Some(',') => {
+                        *self.frames.last_mut().unwrap() = Frame::Object(ObjectState::AwaitingKeyOrEnd);
+                        continue;
+                    }
+                    Some(_) => Err(self.error(ErrorCode::ExpectedComma)),
+                    None => Err(self.error(ErrorCode::EOFWhileParsingObject)),
+                },
+                Some(Frame::Array(ArrayState::AwaitingValueOrEnd)) => {
+                    self.skip_space();
+                    if self.json.peek() == Some(&']') {
+                        self.bump();
+                        self.frames.pop();
+                        self.path.pop();
+                        Ok(JsonEvent::ArrayEnd)
+                    } else {
+                        *self.frames.last_mut().unwrap() = Frame::Array(ArrayState::AwaitingSeparator);
+                        self.value_event()
+                    }
+                }
+                Some(Frame::Array(ArrayState::AwaitingSeparator)) => {
+                    self.skip_space();
+                    match self.bump() {
+                        Some(',') => {
+                            *self.frames.last_mut().unwrap() = Frame::Array(ArrayState::AwaitingValueOrEnd);
+                            if let Some(StackElement::Index(i)) = self.path.last_mut() {
+                                *i += 1;
+                            }
+                            continue;
+                        }
+                        Some(_) => Err(self.error(ErrorCode::ExpectedComma)),
+                        None => Err(self.error(ErrorCode::EOFWhileParsingArray)),
+                    }
+                }
+            };
+            if event.is_err() {
+                self.errored = true;
+            }
+            return Some(event);
+        }
+    }
+}
+
+/// Builds a [`Node`] tree by consuming events from a [`StreamingParser`].
+pub struct Parser<T: Iterator<Item = char>> {
+    events: StreamingParser<T>,
+}
+
+impl<T: Iterator<Item = char>> Parser<T> {
+    pub fn new(json: T) -> Self {
+        Self {
+            events: StreamingParser::new(json),
+        }
+    }
+
+    pub fn parse(&mut self) -> Result<Node, ParserError> {
+        let node = match self.events.next() {
+            Some(Ok(event)) => self.build(event)?,
+            Some(Err(e)) => return Err(e),
+            None => return Err(self.events.error(ErrorCode::EOFWhileParsingValue)),
+        };
+        self.events.skip_space();
+        if self.events.json.peek().is_some() {
+            return Err(self.events.error(ErrorCode::TrailingCharacters));
+        }
+        Ok(node)
+    }
+
+    /// Assembles the `Node` that `event` starts, pulling further events from
+    /// the stream for its children.
+    fn build(&mut self, event: JsonEvent) -> Result<Node, ParserError> {
+        match event {
+            JsonEvent::ObjectStart => {
+                // Our own slot in the path stack sits at `depth - 1`; a nested
+                // container's Start event pushes further slots past it, so
+                // indexing by `depth - 1` (rather than `.last()`) keeps
+                // finding our own current key even while a child is open.
+                let depth = self.events.stack().len();
+                let mut map = HashMap::new();
+                loop {
+                    match self.events.next() {
+                        Some(Ok(JsonEvent::ObjectEnd)) => break,
+                        Some(Ok(event)) => {
+                            let key = match self.events.stack().get(depth - 1) {
+                                Some(StackElement::Key(key)) => key.clone(),
+                                _ => unreachable!("object member without a key on the stack"),
+                            };
+                            let value = self.build(event)?;
+                            map.insert(key, value);
+                        }
+                        Some(Err(e)) => return Err(e),
+                        None => return Err(self.events.error(ErrorCode::EOFWhileParsingObject)),
+                    }
+                }
+                Ok(Node::Object(map))
+            }
+            JsonEvent::ArrayStart => {
+                let mut items = Vec::new();
+                loop {
+                    match self.events.next() {
+                        Some(Ok(JsonEvent::ArrayEnd)) => break,
+                        Some(Ok(event)) => items.push(self.build(event)?),
+                        Some(Err(e)) => return Err(e),
+                        None => return Err(self.events.error(ErrorCode::EOFWhileParsingArray)),
+                    }
+                }
+                Ok(Node::Array(items))
+            }
+            JsonEvent::BooleanValue(b) => Ok(Node::BoolLiteral(b)),
+            JsonEvent::IntValue(n) => Ok(Node::IntLiteral(n)),
+            JsonEvent::FloatValue(f) => Ok(Node::FloatLiteral(f)),
+            JsonEvent::StringValue(s) => Ok(Node::StringLiteral(s)),
+            JsonEvent::NullValue => Ok(Node::NullLiteral),
+            JsonEvent::ObjectEnd | JsonEvent::ArrayEnd => {
+                unreachable!("ObjectEnd/ArrayEnd are consumed by their Start handler")
+            }
+        }
+    }
+}
+
+/// An error produced while decoding a [`Node`] into a Rust type via [`FromJson`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecoderError {
+    ExpectedError(String, String),
+    MissingFieldError(String),
+}
+
+impl fmt::Display for DecoderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecoderError::ExpectedError(expected, found) => {
+                write!(f, "expected {expected}, found {found}")
             }
-            ret.push(self.value());
-            self.skip_space();
-            assert!(self.json.next() == Some(','), "expect , in parsing array");
+            DecoderError::MissingFieldError(name) => write!(f, "missing field `{name}`"),
+        }
+    }
+}
+
+impl std::error::Error for DecoderError {}
+
+/// A short description of `node`'s kind and value, used in `DecoderError` messages.
+fn describe(node: &Node) -> String {
+    match node {
+        Node::IntLiteral(n) => format!("integer `{n}`"),
+        Node::FloatLiteral(f) => format!("float `{f}`"),
+        Node::StringLiteral(s) => format!("string {s:?}"),
+        Node::NullLiteral => "null".to_string(),
+        Node::BoolLiteral(b) => format!("bool `{b}`"),
+        Node::Object(_) => "object".to_string(),
+        Node::Array(_) => "array".to_string(),
+    }
+}
+
+/// Borrows a [`Node`] (or nothing, for a missing struct field) so [`FromJson`]
+/// impls can decode it into a Rust value.
+pub struct Decoder<'a> {
+    node: Option<&'a Node>,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(node: &'a Node) -> Self {
+        Decoder { node: Some(node) }
+    }
+
+    fn absent() -> Self {
+        Decoder { node: None }
+    }
+
+    /// The wrapped node, or `None` if this decoder stands for a missing
+    /// struct field.
+    pub fn node(&self) -> Option<&'a Node> {
+        self.node
+    }
+
+    /// Looks up `name` in this decoder's `Node::Object` and decodes it with
+    /// `f`. If `name` is absent, `f` still runs with an empty decoder, so
+    /// `FromJson` impls such as `Option<T>`'s can treat that as `None`;
+    /// anything else that hits an empty decoder reports a
+    /// `MissingFieldError` naming this field, rather than whatever error `f`
+    /// produced.
+    pub fn read_struct_field<T, F>(&self, name: &str, f: F) -> Result<T, DecoderError>
+    where
+        F: FnOnce(&Decoder) -> Result<T, DecoderError>,
+    {
+        let map = match self.node {
+            Some(Node::Object(map)) => map,
+            Some(other) => return Err(DecoderError::ExpectedError("an object".to_string(), describe(other))),
+            None => return Err(DecoderError::MissingFieldError(name.to_string())),
+        };
+        match map.get(name) {
+            Some(value) => f(&Decoder::new(value)),
+            None => f(&Decoder::absent()).map_err(|_| DecoderError::MissingFieldError(name.to_string())),
+        }
+    }
+}
+
+/// A Rust type that can be decoded from a parsed [`Node`] via a [`Decoder`].
+pub trait FromJson: Sized {
+    fn from_json(decoder: &Decoder) -> Result<Self, DecoderError>;
+}
+
+impl FromJson for i64 {
+    fn from_json(decoder: &Decoder) -> Result<Self, DecoderError> {
+        match decoder.node() {
+            Some(Node::IntLiteral(n)) => Ok(*n),
+            Some(other) => Err(DecoderError::ExpectedError("an integer".to_string(), describe(other))),
+            None => Err(DecoderError::ExpectedError("an integer".to_string(), "nothing".to_string())),
+        }
+    }
+}
+
+impl FromJson for f64 {
+    fn from_json(decoder: &Decoder) -> Result<Self, DecoderError> {
+        match decoder.node() {
+            Some(Node::FloatLiteral(f)) => Ok(*f),
+            Some(Node::IntLiteral(n)) => Ok(*n as f64),
+            Some(other) => Err(DecoderError::ExpectedError("a float".to_string(), describe(other))),
+            None => Err(DecoderError::ExpectedError("a float".to_string(), "nothing".to_string())),
         }
-        Node::Array(ret)
     }
+}
 
-    fn null(&mut self) -> Node {
-        let error_message = "expect null";
-        assert!(self.json.next() == Some('n'), "{}", error_message);
-        assert!(self.json.next() == Some('u'), "{}", error_message);
-        assert!(self.json.next() == Some('l'), "{}", error_message);
-        assert!(self.json.next() == Some('l'), "{}", error_message);
-        Node::NullLiteral
+impl FromJson for bool {
+    fn from_json(decoder: &Decoder) -> Result<Self, DecoderError> {
+        match decoder.node() {
+            Some(Node::BoolLiteral(b)) => Ok(*b),
+            Some(other) => Err(DecoderError::ExpectedError("a bool".to_string(), describe(other))),
+            None => Err(DecoderError::ExpectedError("a bool".to_string(), "nothing".to_string())),
+        }
     }
+}
+
+impl FromJson for String {
+    fn from_json(decoder: &Decoder) -> Result<Self, DecoderError> {
+        match decoder.node() {
+            Some(Node::StringLiteral(s)) => Ok(s.clone()),
+            Some(other) => Err(DecoderError::ExpectedError("a string".to_string(), describe(other))),
+            None => Err(DecoderError::ExpectedError("a string".to_string(), "nothing".to_string())),
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(decoder: &Decoder) -> Result<Self, DecoderError> {
+        match decoder.node() {
+            None | Some(Node::NullLiteral) => Ok(None),
+            Some(_) => T::from_json(decoder).map(Some),
+        }
+    }
+}
 
-    fn parse_true(&mut self) -> Node {
-        let error_message = "expect true";
-        assert!(self.json.next() == Some('t'), "{}", error_message);
-        assert!(self.json.next() == Some('r'), "{}", error_message);
-        assert!(self.json.next() == Some('u'), "{}", error_message);
-        assert!(self.json.next() == Some('e'), "{}", error_message);
-        Node::BoolLiteral(true)
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(decoder: &Decoder) -> Result<Self, DecoderError> {
+        match decoder.node() {
+            Some(Node::Array(items)) => items.iter().map(|item| T::from_json(&Decoder::new(item))).collect(),
+            Some(other) => Err(DecoderError::ExpectedError("an array".to_string(), describe(other))),
+            None => Err(DecoderError::ExpectedError("an array".to_string(), "nothing".to_string())),
+        }
     }
+}
 
-    fn parse_false(&mut self) -> Node {
-        let error_message = "expect false";
-        assert!(self.json.next() == Some('f'), "{}", error_message);
-        assert!(self.json.next() == Some('a'), "{}", error_message);
-        assert!(self.json.next() == Some('l'), "{}", error_message);
-        assert!(self.json.next() == Some('s'), "{}", error_message);
-        assert!(self.json.next() == Some('e'), "{}", error_message);
-        Node::BoolLiteral(false)
+impl<T: FromJson> FromJson for HashMap<String, T> {
+    fn from_json(decoder: &Decoder) -> Result<Self, DecoderError> {
+        match decoder.node() {
+            Some(Node::Object(map)) => map
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), T::from_json(&Decoder::new(value))?)))
+                .collect(),
+            Some(other) => Err(DecoderError::ExpectedError("an object".to_string(), describe(other))),
+            None => Err(DecoderError::ExpectedError("an object".to_string(), "nothing".to_string())),
+        }
     }
 }
 
@@ -152,7 +883,7 @@ mod tests {
     fn test_parse_int_literal() {
         let json_str = "123";
         let mut parser = Parser::new(json_str.chars());
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
         if let Node::IntLiteral(value) = result {
             assert_eq!(value, 123);
         } else {
@@ -164,7 +895,7 @@ mod tests {
     fn test_parse_float_literal() {
         let json_str = "123.45";
         let mut parser = Parser::new(json_str.chars());
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
         if let Node::FloatLiteral(value) = result {
             assert_eq!(value, 123.45);
         } else {
@@ -176,7 +907,7 @@ mod tests {
     fn test_parse_string_literal() {
         let json_str = r#""hello world""#;
         let mut parser = Parser::new(json_str.chars());
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
         if let Node::StringLiteral(value) = result {
             assert_eq!(value, "hello world");
         } else {
@@ -188,7 +919,7 @@ mod tests {
     fn test_parse_null_literal() {
         let json_str = "null";
         let mut parser = Parser::new(json_str.chars());
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
         if let Node::NullLiteral = result {
             // Success
         } else {
@@ -200,7 +931,7 @@ mod tests {
     fn test_parse_empty_object() {
         let json_str = "{}";
         let mut parser = Parser::new(json_str.chars());
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
         if let Node::Object(map) = result {
             assert!(map.is_empty());
         } else {
@@ -212,7 +943,7 @@ mod tests {
     fn test_parse_simple_object() {
         let json_str = r#"{"key": 123,}"#;
         let mut parser = Parser::new(json_str.chars());
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
         if let Node::Object(map) = result {
             assert_eq!(map.len(), 1);
             if let Some(Node::IntLiteral(value)) = map.get("key") {
@@ -229,7 +960,7 @@ mod tests {
     fn test_parse_multi_entry_object() {
         let json_str = r#"{"name": "Alice", "age": 30, "isStudent": false,}"#;
         let mut parser = Parser::new(json_str.chars());
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
         if let Node::Object(map) = result {
             assert_eq!(map.len(), 3);
             if let Some(Node::StringLiteral(name)) = map.get("name") {
@@ -250,7 +981,7 @@ mod tests {
     fn test_parse_empty_array() {
         let json_str = "[]";
         let mut parser = Parser::new(json_str.chars());
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
         if let Node::Array(vec) = result {
             assert!(vec.is_empty());
         } else {
@@ -262,7 +993,7 @@ mod tests {
     fn test_parse_simple_array() {
         let json_str = "[1, 2, 3,]";
         let mut parser = Parser::new(json_str.chars());
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
         if let Node::Array(vec) = result {
             assert_eq!(vec.len(), 3);
             if let Node::IntLiteral(v) = vec[0] { assert_eq!(v, 1); } else { panic!("Expected IntLiteral"); }
@@ -277,7 +1008,7 @@ mod tests {
     fn test_parse_mixed_array() {
         let json_str = r#"[1, "hello", null, 3.14,]"#;
         let mut parser = Parser::new(json_str.chars());
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
         if let Node::Array(vec) = result {
             assert_eq!(vec.len(), 4);
             if let Node::IntLiteral(v) = vec[0] { assert_eq!(v, 1); } else { panic!("Expected IntLiteral"); }
@@ -293,7 +1024,7 @@ mod tests {
     fn test_parse_nested_object() {
         let json_str = r#"{"data": {"id": 1, "name": "Test",},}"#;
         let mut parser = Parser::new(json_str.chars());
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
         if let Node::Object(outer_map) = result {
             if let Some(Node::Object(inner_map)) = outer_map.get("data") {
                 if let Some(Node::IntLiteral(id)) = inner_map.get("id") {
@@ -314,7 +1045,7 @@ mod tests {
     fn test_parse_nested_array() {
         let json_str = r#"[1, [2, 3,], 4,]"#;
         let mut parser = Parser::new(json_str.chars());
-        let result = parser.parse();
+        let result = parser.parse().unwrap();
         if let Node::Array(outer_vec) = result {
             assert_eq!(outer_vec.len(), 3);
             if let Node::IntLiteral(v) = outer_vec[0] { assert_eq!(v, 1); } else { panic!("Expected IntLiteral"); }
@@ -330,4 +1061,517 @@ mod tests {
             panic!("Expected Array, got {:?}", result);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_error_missing_colon() {
+        let json_str = r#"{"key" 123,}"#;
+        let mut parser = Parser::new(json_str.chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::ExpectedColon);
+    }
+
+    #[test]
+    fn test_parse_error_eof_while_parsing_object() {
+        let json_str = r#"{"key": 123"#;
+        let mut parser = Parser::new(json_str.chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::EOFWhileParsingObject);
+    }
+
+    #[test]
+    fn test_parse_error_reports_line_and_column() {
+        let json_str = "{\n  \"key\" 123,\n}";
+        let mut parser = Parser::new(json_str.chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::ExpectedColon);
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn test_parse_error_invalid_syntax() {
+        let json_str = "nope";
+        let mut parser = Parser::new(json_str.chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidSyntax);
+    }
+
+    #[test]
+    fn test_parse_error_trailing_characters_after_scalar() {
+        let json_str = "123 garbage";
+        let mut parser = Parser::new(json_str.chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::TrailingCharacters);
+    }
+
+    #[test]
+    fn test_parse_error_trailing_characters_after_object() {
+        let json_str = r#"{"a": 1,}{"b": 2,}"#;
+        let mut parser = Parser::new(json_str.chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::TrailingCharacters);
+    }
+
+    #[test]
+    fn test_parse_trailing_whitespace_is_allowed() {
+        let json_str = "123 \n\t";
+        let mut parser = Parser::new(json_str.chars());
+        let result = parser.parse().unwrap();
+        assert!(matches!(result, Node::IntLiteral(123)));
+    }
+
+    #[test]
+    fn test_parse_error_key_must_be_a_string() {
+        let json_str = "{1:2}";
+        let mut parser = Parser::new(json_str.chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::KeyMustBeAString);
+    }
+
+    #[test]
+    fn test_to_string_compact_scalars() {
+        assert_eq!(Node::IntLiteral(42).to_string(), "42");
+        assert_eq!(Node::FloatLiteral(1.0).to_string(), "1.0");
+        assert_eq!(Node::FloatLiteral(3.25).to_string(), "3.25");
+        assert_eq!(Node::NullLiteral.to_string(), "null");
+        assert_eq!(Node::BoolLiteral(true).to_string(), "true");
+        assert_eq!(Node::BoolLiteral(false).to_string(), "false");
+    }
+
+    #[test]
+    fn test_to_string_escapes_string_literal() {
+        let node = Node::StringLiteral("a\"b\\c\nd\te\rf\u{1}".to_string());
+        let expected = vec![
+            "\"a", "\\\"", "b", "\\\\", "c", "\\n", "d", "\\t", "e", "\\r", "f", "\\u0001", "\"",
+        ]
+        .concat();
+        assert_eq!(node.to_string(), expected);
+    }
+
+    #[test]
+    fn test_to_string_compact_array() {
+        let node = Node::Array(vec![Node::IntLiteral(1), Node::IntLiteral(2)]);
+        assert_eq!(node.to_string(), "[1,2]");
+    }
+
+    #[test]
+    fn test_to_string_compact_object_sorts_keys() {
+        let mut map = HashMap::new();
+        map.insert("b".to_string(), Node::IntLiteral(2));
+        map.insert("a".to_string(), Node::IntLiteral(1));
+        let node = Node::Object(map);
+        assert_eq!(node.to_string(), r#"{"a":1,"b":2}"#);
+    }
+
+    #[test]
+    fn test_to_string_pretty_nested() {
+        let mut inner = HashMap::new();
+        inner.insert("id".to_string(), Node::IntLiteral(1));
+        let mut outer = HashMap::new();
+        outer.insert("data".to_string(), Node::Object(inner));
+        outer.insert("items".to_string(), Node::Array(vec![Node::IntLiteral(1)]));
+        let node = Node::Object(outer);
+        assert_eq!(
+            node.to_string_pretty(2),
+            "{\n  \"data\": {\n    \"id\": 1\n  },\n  \"items\": [\n    1\n  ]\n}"
+        );
+    }
+
+    #[test]
+    fn test_to_string_pretty_empty_containers() {
+        assert_eq!(Node::Object(HashMap::new()).to_string_pretty(2), "{}");
+        assert_eq!(Node::Array(Vec::new()).to_string_pretty(2), "[]");
+    }
+
+    #[test]
+    fn test_parse_then_to_string_round_trip() {
+        let json_str = r#"{"key": 123,}"#;
+        let mut parser = Parser::new(json_str.chars());
+        let node = parser.parse().unwrap();
+        assert_eq!(node.to_string(), r#"{"key":123}"#);
+    }
+
+    #[test]
+    fn test_parse_string_with_escaped_quote_and_backslash() {
+        let json_str = r#""a\"b\\c""#;
+        let mut parser = Parser::new(json_str.chars());
+        let result = parser.parse().unwrap();
+        if let Node::StringLiteral(value) = result {
+            assert_eq!(value, "a\"b\\c");
+        } else {
+            panic!("Expected StringLiteral, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_string_with_simple_escapes() {
+        let json_str = r#""line\nbreak\ttab""#;
+        let mut parser = Parser::new(json_str.chars());
+        let result = parser.parse().unwrap();
+        if let Node::StringLiteral(value) = result {
+            assert_eq!(value, "line\nbreak\ttab");
+        } else {
+            panic!("Expected StringLiteral, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_string_with_unicode_escape() {
+        let json_str = "\"\\u00e9\"";
+        let mut parser = Parser::new(json_str.chars());
+        let result = parser.parse().unwrap();
+        if let Node::StringLiteral(value) = result {
+            assert_eq!(value, "\u{e9}");
+        } else {
+            panic!("Expected StringLiteral, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_string_with_surrogate_pair() {
+        let json_str = "\"\\ud83d\\ude00\"";
+        let mut parser = Parser::new(json_str.chars());
+        let result = parser.parse().unwrap();
+        if let Node::StringLiteral(value) = result {
+            assert_eq!(value, "\u{1f600}");
+        } else {
+            panic!("Expected StringLiteral, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_string_with_lone_high_surrogate_errors() {
+        let json_str = r#""\ud83d""#;
+        let mut parser = Parser::new(json_str.chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::LoneLeadingSurrogateInHexEscape);
+    }
+
+    #[test]
+    fn test_parse_string_with_lone_low_surrogate_errors() {
+        let json_str = r#""\ude00""#;
+        let mut parser = Parser::new(json_str.chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::LoneLeadingSurrogateInHexEscape);
+    }
+
+    #[test]
+    fn test_parse_string_with_non_hex_digit_errors() {
+        let json_str = r#""\u00zz""#;
+        let mut parser = Parser::new(json_str.chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidEscape);
+    }
+
+    #[test]
+    fn test_parse_string_with_invalid_escape_char_errors() {
+        let json_str = r#""\q""#;
+        let mut parser = Parser::new(json_str.chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidEscape);
+    }
+
+    #[test]
+    fn test_parse_negative_int() {
+        let mut parser = Parser::new("-5".chars());
+        let result = parser.parse().unwrap();
+        if let Node::IntLiteral(value) = result {
+            assert_eq!(value, -5);
+        } else {
+            panic!("Expected IntLiteral, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_exponent_notation() {
+        let mut parser = Parser::new("1e10".chars());
+        let result = parser.parse().unwrap();
+        if let Node::FloatLiteral(value) = result {
+            assert_eq!(value, 1e10);
+        } else {
+            panic!("Expected FloatLiteral, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_negative_exponent_with_fraction() {
+        let mut parser = Parser::new("-3.2E-4".chars());
+        let result = parser.parse().unwrap();
+        if let Node::FloatLiteral(value) = result {
+            assert_eq!(value, -3.2E-4);
+        } else {
+            panic!("Expected FloatLiteral, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_large_integer_fits_i64() {
+        let mut parser = Parser::new("9223372036854775807".chars());
+        let result = parser.parse().unwrap();
+        if let Node::IntLiteral(value) = result {
+            assert_eq!(value, i64::MAX);
+        } else {
+            panic!("Expected IntLiteral, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_integer_too_large_for_i64_becomes_float() {
+        let mut parser = Parser::new("99999999999999999999".chars());
+        let result = parser.parse().unwrap();
+        if let Node::FloatLiteral(_) = result {
+            // Success
+        } else {
+            panic!("Expected FloatLiteral, got {:?}", result);
+        }
+    }
+
+    #[test]
+    fn test_parse_leading_zero_followed_by_digit_errors() {
+        let mut parser = Parser::new("01".chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidNumber);
+    }
+
+    #[test]
+    fn test_parse_bare_minus_errors() {
+        let mut parser = Parser::new("-".chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidNumber);
+    }
+
+    #[test]
+    fn test_parse_dot_with_no_following_digit_errors() {
+        let mut parser = Parser::new("1.".chars());
+        let err = parser.parse().unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidNumber);
+    }
+
+    #[test]
+    fn test_to_string_float_always_has_decimal_point() {
+        assert_eq!(Node::FloatLiteral(1.0).to_string(), "1.0");
+        assert_eq!(Node::FloatLiteral(100.0).to_string(), "100.0");
+    }
+
+    #[test]
+    fn test_streaming_parser_scalar_events() {
+        let events: Vec<_> = StreamingParser::new("42".chars())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(events, vec![JsonEvent::IntValue(42)]);
+    }
+
+    #[test]
+    fn test_streaming_parser_array_events() {
+        let events: Vec<_> = StreamingParser::new("[1, true, null,]".chars())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![
+                JsonEvent::ArrayStart,
+                JsonEvent::IntValue(1),
+                JsonEvent::BooleanValue(true),
+                JsonEvent::NullValue,
+                JsonEvent::ArrayEnd,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_streaming_parser_object_events() {
+        let events: Vec<_> = StreamingParser::new(r#"{"a": 1,}"#.chars())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            events,
+            vec![
+                JsonEvent::ObjectStart,
+                JsonEvent::IntValue(1),
+                JsonEvent::ObjectEnd,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_streaming_parser_reports_stack_path() {
+        let mut parser = StreamingParser::new(r#"{"items": [10, 20,],}"#.chars());
+        assert_eq!(parser.next().unwrap().unwrap(), JsonEvent::ObjectStart);
+        assert_eq!(parser.next().unwrap().unwrap(), JsonEvent::ArrayStart);
+        assert_eq!(
+            parser.stack(),
+            &[StackElement::Key("items".to_string()), StackElement::Index(0)]
+        );
+        assert_eq!(parser.next().unwrap().unwrap(), JsonEvent::IntValue(10));
+        assert_eq!(
+            parser.stack(),
+            &[StackElement::Key("items".to_string()), StackElement::Index(0)]
+        );
+        assert_eq!(parser.next().unwrap().unwrap(), JsonEvent::IntValue(20));
+        assert_eq!(
+            parser.stack(),
+            &[StackElement::Key("items".to_string()), StackElement::Index(1)]
+        );
+        assert_eq!(parser.next().unwrap().unwrap(), JsonEvent::ArrayEnd);
+        assert_eq!(parser.next().unwrap().unwrap(), JsonEvent::ObjectEnd);
+        assert!(parser.next().is_none());
+    }
+
+    #[test]
+    fn test_streaming_parser_surfaces_errors() {
+        let mut parser = StreamingParser::new(r#"{"key" 1,}"#.chars());
+        assert_eq!(parser.next().unwrap().unwrap(), JsonEvent::ObjectStart);
+        let err = parser.next().unwrap().unwrap_err();
+        assert_eq!(err.code, ErrorCode::ExpectedColon);
+    }
+
+    #[test]
+    fn test_tree_parser_builds_nested_object() {
+        let json_str = r#"{"data": {"id": 1, "name": "Test",},}"#;
+        let mut parser = Parser::new(json_str.chars());
+        let node = parser.parse().unwrap();
+        if let Node::Object(outer) = node {
+            if let Some(Node::Object(inner)) = outer.get("data") {
+                assert_eq!(inner.len(), 2);
+            } else {
+                panic!("Expected nested Object for key 'data'");
+            }
+        } else {
+            panic!("Expected Object");
+        }
+    }
+
+    #[test]
+    fn test_from_json_scalars() {
+        assert_eq!(i64::from_json(&Decoder::new(&Node::IntLiteral(42))).unwrap(), 42);
+        assert_eq!(
+            f64::from_json(&Decoder::new(&Node::FloatLiteral(1.5))).unwrap(),
+            1.5
+        );
+        assert_eq!(f64::from_json(&Decoder::new(&Node::IntLiteral(3))).unwrap(), 3.0);
+        assert!(bool::from_json(&Decoder::new(&Node::BoolLiteral(true))).unwrap());
+        assert_eq!(
+            String::from_json(&Decoder::new(&Node::StringLiteral("hi".to_string()))).unwrap(),
+            "hi"
+        );
+    }
+
+    #[test]
+    fn test_from_json_scalar_type_mismatch_errors() {
+        let err = i64::from_json(&Decoder::new(&Node::StringLiteral("nope".to_string()))).unwrap_err();
+        assert!(matches!(err, DecoderError::ExpectedError(_, _)));
+    }
+
+    #[test]
+    fn test_from_json_option_present_and_null() {
+        assert_eq!(
+            Option::<i64>::from_json(&Decoder::new(&Node::IntLiteral(7))).unwrap(),
+            Some(7)
+        );
+        assert_eq!(
+            Option::<i64>::from_json(&Decoder::new(&Node::NullLiteral)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_from_json_vec() {
+        let node = Node::Array(vec![Node::IntLiteral(1), Node::IntLiteral(2)]);
+        let values = Vec::<i64>::from_json(&Decoder::new(&node)).unwrap();
+        assert_eq!(values, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_from_json_hashmap() {
+        let mut map = HashMap::new();
+        map.insert("a".to_string(), Node::IntLiteral(1));
+        let node = Node::Object(map);
+        let decoded = HashMap::<String, i64>::from_json(&Decoder::new(&node)).unwrap();
+        assert_eq!(decoded.get("a"), Some(&1));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Point {
+        x: i64,
+        y: i64,
+        label: Option<String>,
+    }
+
+    impl FromJson for Point {
+        fn from_json(decoder: &Decoder) -> Result<Self, DecoderError> {
+            Ok(Point {
+                x: decoder.read_struct_field("x", FromJson::from_json)?,
+                y: decoder.read_struct_field("y", FromJson::from_json)?,
+                label: decoder.read_struct_field("label", FromJson::from_json)?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_read_struct_field_decodes_struct() {
+        let json_str = r#"{"x": 1, "y": 2,}"#;
+        let mut parser = Parser::new(json_str.chars());
+        let node = parser.parse().unwrap();
+        let point = Point::from_json(&Decoder::new(&node)).unwrap();
+        assert_eq!(point.x, 1);
+        assert_eq!(point.y, 2);
+        assert_eq!(point.label, None);
+    }
+
+    #[test]
+    fn test_read_struct_field_missing_required_errors() {
+        let json_str = r#"{"x": 1,}"#;
+        let mut parser = Parser::new(json_str.chars());
+        let node = parser.parse().unwrap();
+        let err = Point::from_json(&Decoder::new(&node)).unwrap_err();
+        assert_eq!(err, DecoderError::MissingFieldError("y".to_string()));
+    }
+
+    #[test]
+    fn test_get_and_at() {
+        let mut map = HashMap::new();
+        map.insert("name".to_string(), Node::StringLiteral("ferris".to_string()));
+        let object = Node::Object(map);
+        assert_eq!(object.get("name").and_then(Node::as_str), Some("ferris"));
+        assert!(object.get("missing").is_none());
+        assert!(object.at(0).is_none());
+
+        let array = Node::Array(vec![Node::IntLiteral(1), Node::IntLiteral(2)]);
+        assert_eq!(array.at(1).and_then(Node::as_i64), Some(2));
+        assert!(array.at(5).is_none());
+        assert!(array.get("name").is_none());
+    }
+
+    #[test]
+    fn test_pointer_resolves_nested_path() {
+        let json_str = r#"{"data": {"items": [{"name": "first",}, {"name": "second",},],},}"#;
+        let mut parser = Parser::new(json_str.chars());
+        let node = parser.parse().unwrap();
+        assert_eq!(
+            node.pointer("data/items/1/name").and_then(Node::as_str),
+            Some("second")
+        );
+        assert!(node.pointer("data/items/5/name").is_none());
+        assert!(node.pointer("data/missing").is_none());
+        assert!(node.pointer("data").unwrap().as_object().is_some());
+    }
+
+    #[test]
+    fn test_typed_extractors() {
+        assert_eq!(Node::IntLiteral(5).as_i64(), Some(5));
+        assert_eq!(Node::IntLiteral(5).as_f64(), None);
+        assert_eq!(Node::FloatLiteral(1.5).as_f64(), Some(1.5));
+        assert_eq!(Node::BoolLiteral(true).as_bool(), Some(true));
+        assert_eq!(Node::StringLiteral("hi".to_string()).as_str(), Some("hi"));
+        assert_eq!(Node::NullLiteral.as_i64(), None);
+
+        let array = Node::Array(vec![Node::IntLiteral(1)]);
+        assert_eq!(array.as_array().map(Vec::len), Some(1));
+        assert!(array.as_object().is_none());
+
+        let mut map = HashMap::new();
+        map.insert("k".to_string(), Node::IntLiteral(1));
+        let object = Node::Object(map);
+        assert_eq!(object.as_object().map(HashMap::len), Some(1));
+        assert!(object.as_array().is_none());
+    }
+}